@@ -0,0 +1,137 @@
+//! Definición de la interfaz de línea de comandos.
+//!
+//! Usa `clap` (derive) para exponer las banderas estándar que se esperan de
+//! una herramienta pensada para scripts y cron jobs: `--help`, `--version`,
+//! `--yes` (sin prompt interactivo), `--dry-run` y `--quiet`.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+/// Estrategia a seguir cuando el destino de un movimiento ya existe.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, ValueEnum)]
+pub enum PoliticaConflicto {
+    /// Añade un sufijo numérico al nombre, ej. `foto.jpg` → `foto (1).jpg`.
+    Rename,
+    /// Deja el archivo en su ubicación original y lo reporta como omitido.
+    Skip,
+    /// Sobrescribe el archivo existente en el destino.
+    Overwrite,
+}
+
+/// Organiza archivos por extensión en subcarpetas.
+///
+/// SYNOPSIS:
+///     organizador-archivos [OPCIONES] <DIRECTORIO> [EXTENSION]
+///
+/// SUMMARY:
+///     Busca archivos con la extensión indicada dentro de DIRECTORIO y los
+///     mueve a una subcarpeta con el nombre de dicha extensión. Si se omiten
+///     EXTENSION y `--include`, organiza todo el directorio por categorías según el
+///     archivo de configuración del usuario.
+///
+/// LONG_HELP:
+///     Por defecto la herramienta pide confirmación interactiva antes de
+///     mover nada. Use `--yes` para omitir el prompt en scripts y cron jobs,
+///     donde un `read_line` bloqueante provocaría un deadlock. Use
+///     `--dry-run` para listar los archivos que se moverían sin tocar el
+///     sistema de archivos, y `--quiet` para suprimir colores y
+///     notificaciones del sistema. Sin EXTENSION, se usa
+///     `~/.config/organizador-archivos/categorias.toml` para mapear
+///     extensiones a carpetas de destino (creado con valores por defecto si
+///     no existe). Todo movimiento exitoso queda registrado en un journal
+///     que el subcomando `undo` puede revertir.
+#[derive(Parser, Debug)]
+#[command(
+    name = "organizador-archivos",
+    author,
+    version,
+    about = "Organiza archivos por extensión en subcarpetas.",
+    long_about = "SYNOPSIS:\n    organizador-archivos [OPCIONES] <DIRECTORIO> [EXTENSION]\n    organizador-archivos undo <DIRECTORIO>\n\nSUMMARY:\n    Busca archivos con la extensión indicada dentro de DIRECTORIO y los\n    mueve a una subcarpeta con el nombre de dicha extensión. Si se omiten\n    EXTENSION y --include, organiza todo el directorio por categorías según el archivo\n    de configuración del usuario.\n\nLONG_HELP:\n    Por defecto la herramienta pide confirmación interactiva antes de mover\n    nada. Use --yes para omitir el prompt en scripts y cron jobs, donde un\n    read_line bloqueante provocaría un deadlock. Use --dry-run para listar\n    los archivos que se moverían sin tocar el sistema de archivos, y\n    --quiet para suprimir colores y notificaciones del sistema. Sin\n    EXTENSION, se usa ~/.config/organizador-archivos/categorias.toml para\n    mapear extensiones a carpetas de destino (creado con valores por\n    defecto si no existe). Cada movimiento exitoso se registra en\n    .organizador-undo.jsonl dentro del directorio organizado; el subcomando\n    undo revierte el lote más reciente. Use --trash para enviar los\n    archivos encontrados a la papelera del sistema en vez de moverlos."
+)]
+pub struct Cli {
+    /// Subcomando a ejecutar (por ahora, solo `undo`). Si se omite, se
+    /// organiza normalmente usando `directorio`/`extension`.
+    #[command(subcommand)]
+    pub comando: Option<Comando>,
+
+    /// Directorio a escanear. Requerido salvo que se use el subcomando `undo`.
+    pub directorio: Option<PathBuf>,
+
+    /// Extensión de archivo a organizar (con o sin punto inicial).
+    ///
+    /// Si se omite y tampoco se usa `--include`, la herramienta entra en
+    /// modo de categorías: recorre todo
+    /// el directorio y enruta cada archivo a la carpeta de su categoría según
+    /// `~/.config/organizador-archivos/categorias.toml`.
+    pub extension: Option<String>,
+
+    /// No pedir confirmación interactiva antes de mover los archivos.
+    #[arg(short = 'y', long = "yes")]
+    pub yes: bool,
+
+    /// Listar los archivos que se moverían sin modificar el sistema de archivos.
+    #[arg(short = 'n', long = "dry-run")]
+    pub dry_run: bool,
+
+    /// Suprimir colores en la salida y notificaciones del sistema.
+    #[arg(short = 'q', long = "quiet")]
+    pub quiet: bool,
+
+    /// Recorrer subdirectorios en vez de limitarse al nivel superior.
+    #[arg(short = 'r', long = "recursive")]
+    pub recursive: bool,
+
+    /// Profundidad máxima de recorrido cuando se usa `--recursive`.
+    #[arg(long = "max-depth", requires = "recursive", default_value_t = usize::MAX)]
+    pub max_depth: usize,
+
+    /// Lista de extensiones a incluir, separadas por comas (ej. `jpg,png`).
+    ///
+    /// Permite organizar varias extensiones en una sola pasada, cada una en
+    /// su propia carpeta de destino, sin pasar por el modo de categorías.
+    #[arg(long = "include", value_delimiter = ',')]
+    pub include: Vec<String>,
+
+    /// Lista de extensiones a excluir, separadas por comas (ej. `tmp,log`).
+    #[arg(long = "exclude", value_delimiter = ',')]
+    pub exclude: Vec<String>,
+
+    /// Qué hacer cuando el archivo de destino ya existe.
+    #[arg(long = "on-conflict", value_enum, default_value_t = PoliticaConflicto::Rename)]
+    pub on_conflict: PoliticaConflicto,
+
+    /// Enviar los archivos encontrados a la papelera del sistema en vez de
+    /// moverlos a una carpeta de extensión o categoría.
+    #[arg(long = "trash")]
+    pub trash: bool,
+}
+
+/// Subcomandos disponibles además del modo de organización por defecto.
+#[derive(Subcommand, Debug)]
+pub enum Comando {
+    /// Deshace el lote de movimientos más reciente registrado en el journal
+    /// (`.organizador-undo.jsonl`) del directorio indicado.
+    Undo {
+        /// Directorio cuyo journal se va a deshacer.
+        directorio: PathBuf,
+    },
+}
+
+/// Códigos de salida reportados por `main()`.
+pub mod exit_code {
+    /// La operación se completó sin errores.
+    pub const OK: i32 = 0;
+    /// Los argumentos proporcionados no son válidos (directorio inexistente, etc).
+    pub const USO_INVALIDO: i32 = 1;
+    /// Uno o más archivos no se pudieron mover en el modo de extensión. En el
+    /// modo de categorías y con `--trash`, los fallos de archivos individuales
+    /// se informan por stderr sin cambiar el código de salida.
+    pub const ERROR_IO: i32 = 2;
+    /// El modo de categorías terminó con un error: no se pudo leer o crear el
+    /// archivo de configuración, o falló otra operación de E/S de la corrida
+    /// (crear una carpeta de destino, leer la confirmación, escribir el journal).
+    pub const ERROR_CONFIG: i32 = 3;
+    /// El subcomando `undo` no pudo leer o escribir el journal.
+    pub const ERROR_UNDO: i32 = 4;
+}
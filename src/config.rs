@@ -0,0 +1,90 @@
+//! Configuración de categorías para el modo de organización por categorías.
+//!
+//! El usuario puede mapear varias extensiones a una misma carpeta de destino
+//! editando `~/.config/organizador-archivos/categorias.toml`. Si el archivo
+//! no existe, se crea con valores por defecto en el primer uso.
+
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+/// Nombre de la carpeta de destino para extensiones sin categoría asignada.
+pub const CATEGORIA_OTROS: &str = "Otros";
+
+#[derive(Debug, Deserialize)]
+struct CategoriasToml {
+    categorias: HashMap<String, Vec<String>>,
+}
+
+/// Mapa de extensión (en minúsculas, sin punto) a nombre de carpeta de destino.
+pub type MapaCategorias = HashMap<String, String>;
+
+/// Construye la ruta `~/.config/organizador-archivos/categorias.toml`.
+///
+/// Toma la carpeta del usuario de la variable de entorno `$HOME` y devuelve
+/// un error `NotFound` si no está definida.
+fn ruta_config() -> io::Result<PathBuf> {
+    let home = env::var("HOME")
+        .map_err(|_| io::Error::new(io::ErrorKind::NotFound, "La variable de entorno $HOME no está definida"))?;
+
+    let mut ruta = PathBuf::from(home);
+    ruta.push(".config");
+    ruta.push("organizador-archivos");
+    ruta.push("categorias.toml");
+    Ok(ruta)
+}
+
+/// Contenido del archivo de configuración que se escribe en el primer uso.
+fn contenido_por_defecto() -> &'static str {
+    r#"# Configuración de categorías de organizador-archivos.
+# Cada clave es el nombre de la carpeta de destino y el valor es la lista de
+# extensiones (sin punto, en minúsculas) que se mueven a esa carpeta.
+# Las extensiones que no aparezcan aquí van a la carpeta "Otros".
+
+[categorias]
+"Imágenes" = ["jpg", "jpeg", "png", "webp", "gif"]
+"Vídeos" = ["mp4", "mov", "mkv", "avi"]
+"Documentos" = ["pdf", "docx", "doc", "txt", "odt"]
+"#
+}
+
+/// Carga el mapa de categorías, creando el archivo de configuración con
+/// valores por defecto si todavía no existe.
+pub fn cargar_o_crear(categoria_otros: &str) -> io::Result<MapaCategorias> {
+    let ruta = ruta_config()?;
+
+    if !ruta.exists() {
+        if let Some(carpeta_config) = ruta.parent() {
+            fs::create_dir_all(carpeta_config)?;
+        }
+        fs::write(&ruta, contenido_por_defecto())?;
+    }
+
+    let contenido = fs::read_to_string(&ruta)?;
+    let parseado: CategoriasToml = toml::from_str(&contenido)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, format!("categorias.toml inválido: {}", e)))?;
+
+    let mut mapa = MapaCategorias::new();
+    for (categoria, extensiones) in parseado.categorias {
+        if categoria == categoria_otros {
+            continue;
+        }
+        for ext in extensiones {
+            mapa.insert(ext.to_lowercase(), categoria.clone());
+        }
+    }
+
+    Ok(mapa)
+}
+
+/// Devuelve la carpeta de destino para una extensión dada, cayendo de vuelta
+/// a [`CATEGORIA_OTROS`] si no está mapeada.
+pub fn categoria_para_extension(mapa: &MapaCategorias, extension: &str) -> String {
+    mapa.get(&extension.to_lowercase())
+        .cloned()
+        .unwrap_or_else(|| CATEGORIA_OTROS.to_string())
+}
@@ -0,0 +1,140 @@
+//! Journal de transacciones para poder deshacer movimientos.
+//!
+//! Cada corrida que mueve archivos anexa una entrada por archivo movido a
+//! `<directorio>/.organizador-undo.jsonl`. El subcomando `undo` lee ese
+//! journal y revierte el lote más reciente (las entradas con el número de
+//! lote más alto), dejando intactos los lotes anteriores para poder seguir
+//! deshaciendo corrida por corrida. El número de lote es un contador
+//! monótono leído del propio journal, no una marca de tiempo: dos corridas
+//! que caigan en el mismo segundo de reloj seguirían siendo lotes distintos.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// Nombre del archivo de journal dentro del directorio organizado.
+pub const NOMBRE_JOURNAL: &str = ".organizador-undo.jsonl";
+
+/// Una entrada individual del journal: un movimiento `origen -> destino`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EntradaJournal {
+    pub origen: PathBuf,
+    pub destino: PathBuf,
+    pub lote: u64,
+}
+
+/// Resumen de una operación de deshacer.
+#[derive(Debug, Default)]
+pub struct ResumenUndo {
+    pub restaurados: usize,
+    pub fallidos: Vec<(PathBuf, String)>,
+}
+
+fn ruta_journal(directorio: &Path) -> PathBuf {
+    directorio.join(NOMBRE_JOURNAL)
+}
+
+/// Anexa un lote de movimientos exitosos al journal de `directorio`, bajo
+/// un número de lote nuevo (el mayor existente más uno, o 0 si el journal
+/// está vacío o no existe).
+pub fn registrar_lote(directorio: &Path, movimientos: &[(PathBuf, PathBuf)]) -> io::Result<()> {
+    if movimientos.is_empty() {
+        return Ok(());
+    }
+
+    let lote = leer_entradas(directorio)?.iter().map(|e| e.lote).max().map(|m| m + 1).unwrap_or(0);
+
+    use std::io::Write;
+    let mut archivo = fs::OpenOptions::new().create(true).append(true).open(ruta_journal(directorio))?;
+
+    for (origen, destino) in movimientos {
+        let entrada = EntradaJournal { origen: origen.clone(), destino: destino.clone(), lote };
+        let linea = serde_json::to_string(&entrada)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writeln!(archivo, "{}", linea)?;
+    }
+
+    Ok(())
+}
+
+fn leer_entradas(directorio: &Path) -> io::Result<Vec<EntradaJournal>> {
+    let ruta = ruta_journal(directorio);
+    if !ruta.exists() {
+        return Ok(Vec::new());
+    }
+
+    let contenido = fs::read_to_string(ruta)?;
+    contenido
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| serde_json::from_str(l).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)))
+        .collect()
+}
+
+fn escribir_entradas(directorio: &Path, entradas: &[EntradaJournal]) -> io::Result<()> {
+    let ruta = ruta_journal(directorio);
+    if entradas.is_empty() {
+        if ruta.exists() {
+            fs::remove_file(&ruta)?;
+        }
+        return Ok(());
+    }
+
+    let mut contenido = String::new();
+    for entrada in entradas {
+        let linea = serde_json::to_string(entrada).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        contenido.push_str(&linea);
+        contenido.push('\n');
+    }
+    fs::write(ruta, contenido)
+}
+
+/// Deshace el lote más reciente registrado en el journal de `directorio`:
+/// revierte cada `fs::rename(destino, origen)` en orden inverso al que se
+/// registraron en el journal, y reescribe el journal dejando solo los lotes anteriores más
+/// las entradas que no se pudieron restaurar.
+pub fn deshacer_ultimo_lote(directorio: &Path) -> io::Result<ResumenUndo> {
+    let mut entradas = leer_entradas(directorio)?;
+
+    let lote_max = match entradas.iter().map(|e| e.lote).max() {
+        Some(l) => l,
+        None => return Ok(ResumenUndo::default()),
+    };
+
+    let (mut lote_a_deshacer, resto): (Vec<EntradaJournal>, Vec<EntradaJournal>) =
+        entradas.drain(..).partition(|e| e.lote == lote_max);
+    lote_a_deshacer.reverse();
+
+    let mut resumen = ResumenUndo::default();
+    let mut pendientes = resto;
+
+    for entrada in lote_a_deshacer {
+        if !entrada.destino.exists() {
+            resumen.fallidos.push((entrada.origen.clone(), "el archivo ya no está en el destino registrado".to_string()));
+            pendientes.push(entrada);
+            continue;
+        }
+        if entrada.origen.exists() {
+            resumen.fallidos.push((entrada.origen.clone(), "la ubicación original ya está ocupada".to_string()));
+            pendientes.push(entrada);
+            continue;
+        }
+
+        if let Some(carpeta_original) = entrada.origen.parent() {
+            fs::create_dir_all(carpeta_original)?;
+        }
+
+        match fs::rename(&entrada.destino, &entrada.origen) {
+            Ok(()) => resumen.restaurados += 1,
+            Err(e) => {
+                resumen.fallidos.push((entrada.origen.clone(), e.to_string()));
+                pendientes.push(entrada);
+            }
+        }
+    }
+
+    escribir_entradas(directorio, &pendientes)?;
+    Ok(resumen)
+}
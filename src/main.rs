@@ -1,35 +1,47 @@
 //! Herramienta de CLI para organizar automáticamente archivos por extensión.
-//! 
+//!
 //! Esta herramienta escanea un directorio en busca de archivos con una extensión específica
 //! y los mueve a un subdirectorio con el nombre de dicha extensión. Proporciona salida
 //! colorida y notificaciones del sistema para una mejor experiencia de usuario.
 
-use notify_rust::Notification;
-use std::env;
+mod cli;
+mod config;
+mod journal;
+mod organizer;
+mod trash;
+
+use std::collections::HashSet;
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use walkdir::WalkDir;
+use std::process::{Command, ExitCode};
+
+use clap::Parser;
 use colored::*;
+use notify_rust::Notification;
+use walkdir::WalkDir;
+
+use cli::{exit_code, Cli, Comando};
+use config::CATEGORIA_OTROS;
+use organizer::EstadoMovimiento;
 
 /// Envía notificaciones del sistema compatibles con múltiples entornos de escritorio.
-/// 
+///
 /// Esta función intenta diferentes métodos de notificación en orden de preferencia:
 /// 1. notify-rust (multiplataforma)
 /// 2. notify-send (estándar de Linux)
 /// 3. kdialog (KDE Plasma)
 /// 4. zenity (GNOME/MATE/Cinnamon)
 /// 5. Consola como alternativa
-/// 
+///
 /// # Argumentos
-/// 
+///
 /// * `title` - Título de la notificación
 /// * `body` - Cuerpo/mensaje de la notificación
 /// * `icon` - Nombre del icono a mostrar
 fn send_notification(title: &str, body: &str, icon: &str) {
     // Intentar diferentes métodos de notificación según el entorno disponible
-    
+
     // 1. Primero intentar con notify-rust (compatible con la mayoría de escritorios)
     match Notification::new()
         .summary(title)
@@ -43,7 +55,7 @@ fn send_notification(title: &str, body: &str, icon: &str) {
             // Si notify-rust falla, intentar métodos alternativos
         }
     }
-    
+
     // 2. Intentar con notify-send (disponible en la mayoría de sistemas Linux)
     if Command::new("which")
         .arg("notify-send")
@@ -63,7 +75,7 @@ fn send_notification(title: &str, body: &str, icon: &str) {
             return;
         }
     }
-    
+
     // 3. Intentar con kdialog (para KDE)
     if Command::new("which")
         .arg("kdialog")
@@ -82,7 +94,7 @@ fn send_notification(title: &str, body: &str, icon: &str) {
             return;
         }
     }
-    
+
     // 4. Intentar con zenity (para GNOME/MATE/Cinnamon)
     if Command::new("which")
         .arg("zenity")
@@ -103,123 +115,415 @@ fn send_notification(title: &str, body: &str, icon: &str) {
             return;
         }
     }
-    
+
     // 5. Si todo falla, imprimir en consola como alternativa
     println!("\n{} {}: {}", "NOTIFICATION:".bright_yellow().bold(), title, body);
 }
 
-/// Función principal que orquesta el proceso de organización de archivos.
-/// 
-/// # Argumentos
-/// 
-/// * `args[1]` - Directorio a escanear
-/// * `args[2]` - Extensión de archivo a organizar (con o sin punto inicial)
-/// 
-/// # Ejemplo
-/// 
-/// ```bash
-/// organizador-archivos ~/Downloads .MOV
-/// ```
-fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    if args.len() < 3 {
-        // Uso de .bright_red() y .bold()
-        eprintln!("{} {} <directorio> <extension>", "Error:".bright_red().bold(), "Uso:".yellow());
-        eprintln!("Ejemplo: {} /home/leonel/Downloads .MOV", args[0].cyan());
-        return;
-    }
-
-    let ruta_entrada = &args[1];
-    let ext_objetivo = args[2].trim_start_matches('.').to_lowercase();
-    let path_origen = Path::new(ruta_entrada);
-
-    if !path_origen.exists() || !path_origen.is_dir() {
-        eprintln!("{}", "Error: El directorio origen no es válido o no existe.".red().bold());
-        return;
-    }
+/// Recorre `path_origen` y agrupa cada archivo según la categoría de su
+/// extensión, moviéndolo a `path_origen/<categoría>`. Las extensiones sin
+/// categoría asignada caen en [`CATEGORIA_OTROS`]. Los archivos sin
+/// extensión y los ocultos no se tocan.
+fn organizar_por_categorias(cli: &Cli, path_origen: &Path) -> io::Result<()> {
+    let mapa = config::cargar_o_crear(CATEGORIA_OTROS)?;
 
-    let mut archivos_encontrados = Vec::new();
+    let exclusiones: HashSet<String> = cli.exclude.iter().map(|e| e.trim_start_matches('.').to_lowercase()).collect();
+    let profundidad = if cli.recursive { cli.max_depth } else { 1 };
 
-    for entrada in WalkDir::new(path_origen).max_depth(1).into_iter().filter_map(|e| e.ok()) {
+    let mut archivos_por_categoria: Vec<(PathBuf, String)> = Vec::new();
+    for entrada in WalkDir::new(path_origen).max_depth(profundidad).into_iter().filter_map(|e| e.ok()) {
         let path = entrada.path();
-        if path.is_file() {
+        if path.is_file() && !organizer::es_oculto(path) {
             if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                if ext.to_lowercase() == ext_objetivo {
-                    archivos_encontrados.push(path.to_path_buf());
+                if exclusiones.contains(&ext.to_lowercase()) {
+                    continue;
                 }
+                let categoria = config::categoria_para_extension(&mapa, ext);
+                archivos_por_categoria.push((path.to_path_buf(), categoria));
             }
         }
     }
 
-    if archivos_encontrados.is_empty() {
-        println!("{} No se encontraron archivos con la extensión: .{}", "info:".blue(), ext_objetivo.yellow());
-        return;
+    if archivos_por_categoria.is_empty() {
+        println!("{} No se encontraron archivos para organizar.", "info:".blue());
+        return Ok(());
     }
 
-    // Mensaje con colores
     println!(
-        "\n{} Se encontraron {} archivos {} en {}",
+        "\n{} Se encontraron {} archivos para organizar por categoría en {}",
         "FOUND:".bright_green().bold(),
-        archivos_encontrados.len().to_string().yellow(),
-        format!(".{}", ext_objetivo).cyan(),
-        ruta_entrada.blue()
+        archivos_por_categoria.len().to_string().yellow(),
+        path_origen.display().to_string().blue()
     );
 
-    print!("{} [s/N]: ", "¿Confirmas mover estos archivos?".bold());
-    io::stdout().flush().unwrap();
+    if cli.dry_run {
+        for (ruta_archivo, categoria) in &archivos_por_categoria {
+            println!("  {} {:?} {} {}", "→".cyan(), ruta_archivo.file_name().unwrap(), "→".cyan(), categoria.cyan());
+        }
+        println!(
+            "\n{} Se moverían {} archivos (dry-run, no se modificó nada).",
+            "DRY-RUN:".bright_blue().bold(),
+            archivos_por_categoria.len().to_string().yellow().bold()
+        );
+        return Ok(());
+    }
+
+    if !cli.yes {
+        print!("{} [s/N]: ", "¿Confirmas mover estos archivos?".bold());
+        io::stdout().flush()?;
 
-    let mut respuesta = String::new();
-    io::stdin().read_line(&mut respuesta).unwrap();
+        let mut respuesta = String::new();
+        io::stdin().read_line(&mut respuesta)?;
 
-    if respuesta.trim().to_lowercase() != "s" {
-        println!("{}", "Operación cancelada por el usuario.".yellow());
-        return;
+        if respuesta.trim().to_lowercase() != "s" {
+            println!("{}", "Operación cancelada por el usuario.".yellow());
+            return Ok(());
+        }
     }
 
-    let mut ruta_destino = PathBuf::from(path_origen);
-    ruta_destino.push(&ext_objetivo);
+    if cli.trash {
+        let mut enviados = 0;
+        for (ruta_archivo, _categoria) in archivos_por_categoria {
+            let nombre_archivo = ruta_archivo.file_name().unwrap().to_owned();
+            match trash::enviar_a_papelera(&ruta_archivo) {
+                Ok(()) => {
+                    println!("  {} {:?}", "🗑".red(), nombre_archivo);
+                    enviados += 1;
+                }
+                Err(e) => eprintln!("  {} {:?}: {}", "✘".red(), nombre_archivo, e),
+            }
+        }
+
+        println!(
+            "\n{} Se enviaron {} archivos a la papelera.",
+            "FINALIZADO:".on_green().white().bold(),
+            enviados.to_string().yellow().bold()
+        );
+
+        if !cli.quiet && enviados > 0 {
+            send_notification(
+                "Organizador de Archivos",
+                &format!("Se enviaron {} archivos a la papelera.", enviados),
+                "user-trash"
+            );
+        }
 
-    if !ruta_destino.exists() {
-        fs::create_dir(&ruta_destino).expect("No se pudo crear la carpeta");
-        println!("{} Carpeta creada: {:?}", "OK:".green(), ruta_destino);
+        return Ok(());
     }
 
     let mut movidos = 0;
-    for ruta_archivo in archivos_encontrados {
-        let nombre_archivo = ruta_archivo.file_name().unwrap();
-        let mut destino_final = ruta_destino.clone();
-        destino_final.push(nombre_archivo);
-
-        match fs::rename(&ruta_archivo, destino_final) {
-            Ok(_) => {
-                // Imprimimos el nombre del archivo en verde claro
-                println!("  {} {:?}", "✔".green(), nombre_archivo);
+    let mut renombrados = 0;
+    let mut omitidos = 0;
+    let mut movimientos_para_journal: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for (ruta_archivo, categoria) in archivos_por_categoria {
+        let mut ruta_destino = PathBuf::from(path_origen);
+        ruta_destino.push(&categoria);
+
+        if !ruta_destino.exists() {
+            fs::create_dir_all(&ruta_destino)?;
+        }
+
+        let nombre_archivo = ruta_archivo.file_name().unwrap().to_owned();
+        let mut destino_deseado = ruta_destino.clone();
+        destino_deseado.push(&nombre_archivo);
+
+        let estado = organizer::mover_uno(&ruta_archivo, destino_deseado.clone(), cli.on_conflict);
+        if let Some(destino_final) = estado.destino_exitoso(&destino_deseado) {
+            movimientos_para_journal.push((ruta_archivo.clone(), destino_final.to_path_buf()));
+        }
+
+        match estado {
+            EstadoMovimiento::Movido => {
+                println!("  {} {:?} {} {}", "✔".green(), nombre_archivo, "→".green(), categoria.cyan());
                 movidos += 1;
             }
-            Err(e) => eprintln!("  {} {:?}: {}", "✘".red(), nombre_archivo, e),
+            EstadoMovimiento::Renombrado(destino_final) => {
+                println!(
+                    "  {} {:?} {} {} ({})",
+                    "↻".yellow(),
+                    nombre_archivo,
+                    "→".yellow(),
+                    categoria.cyan(),
+                    format!("renombrado a {:?}", destino_final.file_name().unwrap()).yellow()
+                );
+                movidos += 1;
+                renombrados += 1;
+            }
+            EstadoMovimiento::Omitido => {
+                println!("  {} {:?} {}", "⚠".yellow(), nombre_archivo, "ya existe en el destino, omitido".yellow());
+                omitidos += 1;
+            }
+            EstadoMovimiento::Error(e) => eprintln!("  {} {:?}: {}", "✘".red(), nombre_archivo, e),
         }
     }
 
+    if !movimientos_para_journal.is_empty() {
+        journal::registrar_lote(path_origen, &movimientos_para_journal)?;
+    }
+
     println!(
-        "\n{} Se movieron {} archivos a la carpeta {}.",
+        "\n{} Se movieron {} archivos a sus carpetas de categoría ({} renombrados, {} omitidos por conflicto).",
         "FINALIZADO:".on_green().white().bold(),
         movidos.to_string().yellow().bold(),
-        ext_objetivo.cyan()
+        renombrados.to_string().yellow(),
+        omitidos.to_string().yellow()
     );
 
-    if movidos > 0 {
-        // Enviar notificación nativa compatible con múltiples escritorios
-        send_notification(
-            "Organizador de Archivos",
-            &format!("¡Éxito! Se movieron {} archivos a la carpeta '{}'.", movidos, ext_objetivo),
-            "folder-download"
+    if !cli.quiet {
+        if movidos > 0 {
+            send_notification(
+                "Organizador de Archivos",
+                &format!(
+                    "¡Éxito! Se organizaron {} archivos por categoría ({} renombrados, {} omitidos).",
+                    movidos, renombrados, omitidos
+                ),
+                "folder-download"
+            );
+        } else {
+            send_notification("Organizador de Archivos", "No se realizó ningún movimiento.", "info");
+        }
+    }
+
+    Ok(())
+}
+
+/// Ejecuta el subcomando `undo`: deshace el lote de movimientos más
+/// reciente registrado en el journal del directorio indicado.
+fn ejecutar_undo(directorio: &Path, quiet: bool) -> ExitCode {
+    if !directorio.exists() || !directorio.is_dir() {
+        eprintln!("{}", "Error: El directorio no es válido o no existe.".red().bold());
+        return ExitCode::from(exit_code::USO_INVALIDO as u8);
+    }
+
+    match journal::deshacer_ultimo_lote(directorio) {
+        Ok(resumen) if resumen.restaurados == 0 && resumen.fallidos.is_empty() => {
+            println!("{} No hay ningún lote de movimientos para deshacer.", "info:".blue());
+            ExitCode::from(exit_code::OK as u8)
+        }
+        Ok(resumen) => {
+            println!(
+                "{} Se restauraron {} archivos a su ubicación original.",
+                "FINALIZADO:".on_green().white().bold(),
+                resumen.restaurados.to_string().yellow().bold()
+            );
+            for (origen, motivo) in &resumen.fallidos {
+                eprintln!("  {} {:?}: {}", "✘".red(), origen, motivo);
+            }
+
+            if !quiet && resumen.restaurados > 0 {
+                send_notification(
+                    "Organizador de Archivos",
+                    &format!("Se deshicieron {} movimientos.", resumen.restaurados),
+                    "edit-undo"
+                );
+            }
+
+            if resumen.fallidos.is_empty() {
+                ExitCode::from(exit_code::OK as u8)
+            } else {
+                ExitCode::from(exit_code::ERROR_UNDO as u8)
+            }
+        }
+        Err(e) => {
+            eprintln!("{} {}", "Error:".bright_red().bold(), e);
+            ExitCode::from(exit_code::ERROR_UNDO as u8)
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    if cli.quiet {
+        colored::control::set_override(false);
+    }
+
+    if let Some(Comando::Undo { directorio }) = &cli.comando {
+        return ejecutar_undo(directorio, cli.quiet);
+    }
+
+    let directorio = match &cli.directorio {
+        Some(d) => d,
+        None => {
+            eprintln!("{} {} <directorio> [extension]", "Error:".bright_red().bold(), "Uso:".yellow());
+            return ExitCode::from(exit_code::USO_INVALIDO as u8);
+        }
+    };
+
+    let path_origen = Path::new(directorio);
+
+    if !path_origen.exists() || !path_origen.is_dir() {
+        eprintln!("{}", "Error: El directorio origen no es válido o no existe.".red().bold());
+        return ExitCode::from(exit_code::USO_INVALIDO as u8);
+    }
+
+    let mut extensiones: HashSet<String> = HashSet::new();
+    if let Some(ext) = &cli.extension {
+        extensiones.insert(ext.trim_start_matches('.').to_lowercase());
+    }
+    for ext in &cli.include {
+        extensiones.insert(ext.trim_start_matches('.').to_lowercase());
+    }
+    let exclusiones: HashSet<String> = cli.exclude.iter().map(|e| e.trim_start_matches('.').to_lowercase()).collect();
+    extensiones.retain(|e| !exclusiones.contains(e));
+
+    if extensiones.is_empty() {
+        return match organizar_por_categorias(&cli, path_origen) {
+            Ok(()) => ExitCode::from(exit_code::OK as u8),
+            Err(e) => {
+                eprintln!("{} {}", "Error:".bright_red().bold(), e);
+                ExitCode::from(exit_code::ERROR_CONFIG as u8)
+            }
+        };
+    }
+
+    let archivos_encontrados = organizer::recolectar(path_origen, cli.recursive, cli.max_depth, &extensiones, &exclusiones);
+
+    if archivos_encontrados.is_empty() {
+        println!(
+            "{} No se encontraron archivos con las extensiones solicitadas en {}",
+            "info:".blue(),
+            directorio.display().to_string().blue()
         );
-    } else {
-        send_notification(
-            "Organizador de Archivos",
-            "No se realizó ningún movimiento.",
-            "info"
+        return ExitCode::from(exit_code::OK as u8);
+    }
+
+    // Mensaje con colores
+    println!(
+        "\n{} Se encontraron {} archivos en {}",
+        "FOUND:".bright_green().bold(),
+        archivos_encontrados.len().to_string().yellow(),
+        directorio.display().to_string().blue()
+    );
+
+    if cli.dry_run {
+        for archivo in &archivos_encontrados {
+            println!("  {} {} {} {}/", "→".cyan(), archivo.relativo.display(), "→".cyan(), archivo.extension.cyan());
+        }
+        println!(
+            "\n{} Se moverían {} archivos (dry-run, no se modificó nada).",
+            "DRY-RUN:".bright_blue().bold(),
+            archivos_encontrados.len().to_string().yellow().bold()
         );
+        return ExitCode::from(exit_code::OK as u8);
     }
-}
\ No newline at end of file
+
+    if !cli.yes {
+        print!("{} [s/N]: ", "¿Confirmas mover estos archivos?".bold());
+        io::stdout().flush().unwrap();
+
+        let mut respuesta = String::new();
+        io::stdin().read_line(&mut respuesta).unwrap();
+
+        if respuesta.trim().to_lowercase() != "s" {
+            println!("{}", "Operación cancelada por el usuario.".yellow());
+            return ExitCode::from(exit_code::OK as u8);
+        }
+    }
+
+    if cli.trash {
+        let mut enviados = 0;
+        for archivo in &archivos_encontrados {
+            match trash::enviar_a_papelera(&archivo.origen) {
+                Ok(()) => {
+                    println!("  {} {}", "🗑".red(), archivo.relativo.display());
+                    enviados += 1;
+                }
+                Err(e) => eprintln!("  {} {}: {}", "✘".red(), archivo.relativo.display(), e),
+            }
+        }
+
+        println!(
+            "\n{} Se enviaron {} archivos a la papelera.",
+            "FINALIZADO:".on_green().white().bold(),
+            enviados.to_string().yellow().bold()
+        );
+
+        if !cli.quiet && enviados > 0 {
+            send_notification(
+                "Organizador de Archivos",
+                &format!("Se enviaron {} archivos a la papelera.", enviados),
+                "user-trash"
+            );
+        }
+
+        return ExitCode::from(exit_code::OK as u8);
+    }
+
+    let (movidos, resultados) = organizer::mover_en_paralelo(archivos_encontrados, path_origen, cli.on_conflict);
+
+    let mut hubo_errores = false;
+    let mut renombrados = 0;
+    let mut omitidos = 0;
+    let mut movimientos_para_journal: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for resultado in &resultados {
+        let mut destino_planeado = PathBuf::from(path_origen);
+        destino_planeado.push(&resultado.extension);
+        destino_planeado.push(&resultado.relativo);
+        if let Some(destino_final) = resultado.estado.destino_exitoso(&destino_planeado) {
+            movimientos_para_journal.push((resultado.origen.clone(), destino_final.to_path_buf()));
+        }
+
+        match &resultado.estado {
+            EstadoMovimiento::Movido => {
+                println!("  {} {} {} {}/", "✔".green(), resultado.relativo.display(), "→".green(), resultado.extension.cyan())
+            }
+            EstadoMovimiento::Renombrado(destino_final) => {
+                println!(
+                    "  {} {} {} {}/ ({})",
+                    "↻".yellow(),
+                    resultado.relativo.display(),
+                    "→".yellow(),
+                    resultado.extension.cyan(),
+                    format!("renombrado a {:?}", destino_final.file_name().unwrap()).yellow()
+                );
+                renombrados += 1;
+            }
+            EstadoMovimiento::Omitido => {
+                println!("  {} {} {}", "⚠".yellow(), resultado.relativo.display(), "ya existe en el destino, omitido".yellow());
+                omitidos += 1;
+            }
+            EstadoMovimiento::Error(e) => {
+                eprintln!("  {} {}: {}", "✘".red(), resultado.relativo.display(), e);
+                hubo_errores = true;
+            }
+        }
+    }
+
+    if !movimientos_para_journal.is_empty() {
+        if let Err(e) = journal::registrar_lote(path_origen, &movimientos_para_journal) {
+            eprintln!("{} No se pudo registrar el journal de deshacer: {}", "Aviso:".yellow().bold(), e);
+        }
+    }
+
+    println!(
+        "\n{} Se movieron {} archivos ({} renombrados, {} omitidos por conflicto).",
+        "FINALIZADO:".on_green().white().bold(),
+        movidos.to_string().yellow().bold(),
+        renombrados.to_string().yellow(),
+        omitidos.to_string().yellow()
+    );
+
+    if !cli.quiet {
+        if movidos > 0 {
+            // Enviar notificación nativa compatible con múltiples escritorios
+            send_notification(
+                "Organizador de Archivos",
+                &format!("¡Éxito! Se movieron {} archivos ({} renombrados, {} omitidos).", movidos, renombrados, omitidos),
+                "folder-download"
+            );
+        } else {
+            send_notification(
+                "Organizador de Archivos",
+                "No se realizó ningún movimiento.",
+                "info"
+            );
+        }
+    }
+
+    if hubo_errores {
+        ExitCode::from(exit_code::ERROR_IO as u8)
+    } else {
+        ExitCode::from(exit_code::OK as u8)
+    }
+}
@@ -0,0 +1,244 @@
+//! Recolección y movimiento de archivos para el modo de extensión única /
+//! múltiples extensiones (`--include`/`--exclude`), con soporte opcional de
+//! recorrido recursivo, movimientos en paralelo vía `rayon` y resolución de
+//! conflictos de destino.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+use rayon::prelude::*;
+use walkdir::WalkDir;
+
+use crate::cli::PoliticaConflicto;
+
+/// Los archivos ocultos (dotfiles) nunca se recolectan para organizar o
+/// mover: por convención de Unix, y porque el propio journal de deshacer
+/// (`.organizador-undo.jsonl`) vive como dotfile en el directorio
+/// organizado, así que sin este filtro una segunda pasada lo barrería como
+/// un archivo más y `undo` dejaría de encontrarlo.
+pub fn es_oculto(path: &Path) -> bool {
+    path.file_name().and_then(|n| n.to_str()).map(|n| n.starts_with('.')).unwrap_or(false)
+}
+
+/// Un archivo encontrado durante el recorrido, junto con la información
+/// necesaria para reconstruir su destino.
+pub struct ArchivoEncontrado {
+    /// Ruta del archivo en su ubicación original, bajo `path_origen` (relativa
+    /// si `path_origen` lo es).
+    pub origen: PathBuf,
+    /// Ruta relativa a `path_origen`, preservada para no colisionar ni
+    /// aplanar la estructura en recorridos recursivos.
+    pub relativo: PathBuf,
+    /// Extensión en minúsculas, sin el punto inicial.
+    pub extension: String,
+}
+
+/// Cómo se resolvió el movimiento de un archivo.
+pub enum EstadoMovimiento {
+    /// Se movió tal cual al destino planeado.
+    Movido,
+    /// El destino ya existía y se renombró automáticamente a esta ruta.
+    Renombrado(PathBuf),
+    /// El destino ya existía y, según la política de conflicto, se omitió.
+    Omitido,
+    /// El movimiento falló.
+    Error(io::Error),
+}
+
+impl EstadoMovimiento {
+    /// Devuelve el destino real al que terminó el archivo, si el movimiento
+    /// tuvo éxito. `planeado` es la ruta de destino antes de resolver
+    /// conflictos, usada cuando no hubo renombrado.
+    pub fn destino_exitoso<'a>(&'a self, planeado: &'a Path) -> Option<&'a Path> {
+        match self {
+            EstadoMovimiento::Movido => Some(planeado),
+            EstadoMovimiento::Renombrado(destino) => Some(destino.as_path()),
+            EstadoMovimiento::Omitido | EstadoMovimiento::Error(_) => None,
+        }
+    }
+}
+
+/// Resultado de intentar mover un [`ArchivoEncontrado`].
+pub struct ResultadoMovimiento {
+    pub origen: PathBuf,
+    pub relativo: PathBuf,
+    pub extension: String,
+    pub estado: EstadoMovimiento,
+}
+
+/// Recorre `path_origen` y recolecta los archivos cuya extensión está en
+/// `extensiones` (y no en `exclusiones`). Con `recursive` en `false` el
+/// recorrido se limita al nivel superior, igual que antes.
+pub fn recolectar(
+    path_origen: &Path,
+    recursive: bool,
+    max_depth: usize,
+    extensiones: &HashSet<String>,
+    exclusiones: &HashSet<String>,
+) -> Vec<ArchivoEncontrado> {
+    let profundidad = if recursive { max_depth } else { 1 };
+
+    WalkDir::new(path_origen)
+        .max_depth(profundidad)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|entrada| {
+            let path = entrada.path();
+            if !path.is_file() || es_oculto(path) {
+                return None;
+            }
+
+            let ext = path.extension().and_then(|s| s.to_str())?.to_lowercase();
+            if !extensiones.contains(&ext) || exclusiones.contains(&ext) {
+                return None;
+            }
+
+            let relativo = path.strip_prefix(path_origen).ok()?.to_path_buf();
+            Some(ArchivoEncontrado {
+                origen: path.to_path_buf(),
+                relativo,
+                extension: ext,
+            })
+        })
+        .collect()
+}
+
+/// Busca un nombre libre añadiendo un sufijo numérico antes de la extensión,
+/// ej. `foto.jpg` → `foto (1).jpg` → `foto (2).jpg`, ...
+fn nombre_sin_colision(destino: &Path) -> PathBuf {
+    let carpeta = destino.parent().unwrap_or_else(|| Path::new(""));
+    let stem = destino.file_stem().and_then(|s| s.to_str()).unwrap_or("archivo");
+    let extension = destino.extension().and_then(|s| s.to_str());
+
+    let mut intento = 1u32;
+    loop {
+        let nombre = match extension {
+            Some(ext) => format!("{} ({}).{}", stem, intento, ext),
+            None => format!("{} ({})", stem, intento),
+        };
+        let candidato = carpeta.join(nombre);
+        if !candidato.exists() {
+            return candidato;
+        }
+        intento += 1;
+    }
+}
+
+/// Resuelve el destino final de un movimiento según la política de
+/// conflicto, devolviendo `None` cuando el archivo debe omitirse.
+///
+/// La comprobación de existencia y el `rename` no son atómicos entre sí, así
+/// que los llamadores que resuelven destinos desde varios hilos a la vez
+/// (como [`mover_en_paralelo`]) deben serializar las llamadas a esta función
+/// junto con el `rename` correspondiente para que dos archivos no terminen
+/// eligiendo el mismo nombre libre y uno sobrescriba al otro.
+fn resolver_destino(destino: PathBuf, politica: PoliticaConflicto) -> Option<(PathBuf, bool)> {
+    if !destino.exists() {
+        return Some((destino, false));
+    }
+
+    match politica {
+        PoliticaConflicto::Overwrite => Some((destino, false)),
+        PoliticaConflicto::Skip => None,
+        PoliticaConflicto::Rename => Some((nombre_sin_colision(&destino), true)),
+    }
+}
+
+/// Mueve cada archivo a `path_origen/<extensión>/<subruta relativa>`,
+/// preservando la subruta original para que los recorridos recursivos no
+/// aplanen ni colisionen archivos de mismo nombre en subcarpetas distintas.
+/// Los conflictos de destino se resuelven según `politica`.
+///
+/// Las carpetas de destino se crean por adelantado (secuencialmente, ya que
+/// `fs::create_dir_all` no es idempotente de forma segura en paralelo). Los
+/// archivos se reparten entre hilos con `rayon`, pero la
+/// resolución de conflictos (comprobar si el destino existe, elegir un
+/// nombre libre) y el `rename` que la concreta se serializan bajo un mutex
+/// compartido: si dos hilos resolvieran destinos para archivos con el mismo
+/// nombre a la vez, ambos podrían decidir el mismo `foto (1).jpg` libre y
+/// el segundo `rename` pisaría al primero. El resto del trabajo de cada
+/// hilo (construir la ruta planeada, contabilizar resultados) no necesita
+/// el mutex y sigue en paralelo.
+pub fn mover_en_paralelo(
+    archivos: Vec<ArchivoEncontrado>,
+    path_origen: &Path,
+    politica: PoliticaConflicto,
+) -> (usize, Vec<ResultadoMovimiento>) {
+    let carpetas_destino: HashSet<PathBuf> = archivos
+        .iter()
+        .map(|a| {
+            let mut carpeta = PathBuf::from(path_origen);
+            carpeta.push(&a.extension);
+            if let Some(subcarpeta) = a.relativo.parent() {
+                carpeta.push(subcarpeta);
+            }
+            carpeta
+        })
+        .collect();
+
+    for carpeta in &carpetas_destino {
+        let _ = fs::create_dir_all(carpeta);
+    }
+
+    let movidos = AtomicUsize::new(0);
+    let resolucion = Mutex::new(());
+
+    let resultados: Vec<ResultadoMovimiento> = archivos
+        .into_par_iter()
+        .map(|archivo| {
+            let mut destino_planeado = PathBuf::from(path_origen);
+            destino_planeado.push(&archivo.extension);
+            destino_planeado.push(&archivo.relativo);
+
+            let estado = {
+                let _guard = resolucion.lock().unwrap();
+                match resolver_destino(destino_planeado, politica) {
+                    None => EstadoMovimiento::Omitido,
+                    Some((destino_final, fue_renombrado)) => match fs::rename(&archivo.origen, &destino_final) {
+                        Ok(()) => {
+                            movidos.fetch_add(1, Ordering::Relaxed);
+                            if fue_renombrado {
+                                EstadoMovimiento::Renombrado(destino_final)
+                            } else {
+                                EstadoMovimiento::Movido
+                            }
+                        }
+                        Err(e) => EstadoMovimiento::Error(e),
+                    },
+                }
+            };
+
+            ResultadoMovimiento {
+                origen: archivo.origen,
+                relativo: archivo.relativo,
+                extension: archivo.extension,
+                estado,
+            }
+        })
+        .collect();
+
+    (movidos.load(Ordering::Relaxed), resultados)
+}
+
+/// Mueve un único archivo a `destino_deseado`, resolviendo el conflicto según
+/// `politica`. Usado por el modo de categorías, donde los movimientos son
+/// secuenciales.
+pub fn mover_uno(origen: &Path, destino_deseado: PathBuf, politica: PoliticaConflicto) -> EstadoMovimiento {
+    match resolver_destino(destino_deseado, politica) {
+        None => EstadoMovimiento::Omitido,
+        Some((destino_final, fue_renombrado)) => match fs::rename(origen, &destino_final) {
+            Ok(()) => {
+                if fue_renombrado {
+                    EstadoMovimiento::Renombrado(destino_final)
+                } else {
+                    EstadoMovimiento::Movido
+                }
+            }
+            Err(e) => EstadoMovimiento::Error(e),
+        },
+    }
+}
@@ -0,0 +1,55 @@
+//! Backend de "enviar a la papelera" compatible con múltiples entornos.
+//!
+//! Igual que `send_notification`, prueba distintos métodos en orden de
+//! preferencia hasta que uno funcione:
+//! 1. El crate `trash` (multiplataforma, usa las APIs nativas de cada SO)
+//! 2. `gio trash` (GNOME/GLib)
+//! 3. `kioclient5 move <archivo> trash:/` (KDE Plasma)
+//! 4. `trash-put` (trash-cli, genérico en Linux)
+
+use std::io;
+use std::path::Path;
+use std::process::Command;
+
+fn comando_disponible(nombre: &str) -> bool {
+    Command::new("which").arg(nombre).output().map(|o| o.status.success()).unwrap_or(false)
+}
+
+/// Envía `ruta` a la papelera del sistema en vez de borrarla permanentemente.
+pub fn enviar_a_papelera(ruta: &Path) -> io::Result<()> {
+    // 1. Primero intentar con el crate `trash` (multiplataforma)
+    if trash::delete(ruta).is_ok() {
+        return Ok(());
+    }
+
+    // 2. Intentar con `gio trash` (disponible en la mayoría de escritorios GNOME)
+    if comando_disponible("gio")
+        && Command::new("gio").arg("trash").arg(ruta).output().map(|o| o.status.success()).unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    // 3. Intentar con `kioclient5` (para KDE Plasma)
+    if comando_disponible("kioclient5")
+        && Command::new("kioclient5")
+            .arg("move")
+            .arg(ruta)
+            .arg("trash:/")
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    // 4. Intentar con `trash-put` (trash-cli)
+    if comando_disponible("trash-put")
+        && Command::new("trash-put").arg(ruta).output().map(|o| o.status.success()).unwrap_or(false)
+    {
+        return Ok(());
+    }
+
+    Err(io::Error::other(
+        "No se encontró ningún backend de papelera disponible (trash, gio, kioclient5, trash-put)",
+    ))
+}